@@ -1,12 +1,16 @@
 //! In-memory corpus, keeps all test cases in memory at all times
 
-use core::cell::RefCell;
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
     corpus::{Corpus, CorpusId, Testcase},
-    inputs::{Input, UsesInput},
+    inputs::{HasLen, Input, UsesInput},
     Error,
 };
 
@@ -85,29 +89,29 @@ where
         idx
     }
     
+    /// Removes a testcase by id, repairing the neighboring `prev`/`next` links so the
+    /// insertion-order chain stays hole-free (see [`Self::range`]).
     #[cfg(not(feature = "corpus_btreemap"))]
-    pub fn remove(&self, idx: CorpusId) -> Option<&RefCell<Testcase<I>>> {
-        if let Some(item) = self.map.remove(&idx) {
-            if let Some(prev) = item.prev {
-                self.map.get(&prev).unwrap().next = item.next;
-            } else {
-                // first elem
-                self.first_idx = item.next;
-            }
-            if let Some(next) = item.next {
-                self.map.get(&next).unwrap().prev = item.prev;
-            } else {
-                // last elem
-                self.last_idx = item.prev;
-            }
-            Some(item)
+    pub fn remove(&mut self, idx: CorpusId) -> Option<RefCell<Testcase<I>>> {
+        let item = self.map.remove(&idx)?;
+        if let Some(prev) = item.prev {
+            self.map.get_mut(&prev).unwrap().next = item.next;
         } else {
-            None
+            // first elem
+            self.first_idx = item.next;
         }
+        if let Some(next) = item.next {
+            self.map.get_mut(&next).unwrap().prev = item.prev;
+        } else {
+            // last elem
+            self.last_idx = item.prev;
+        }
+        Some(item.testcase)
     }
 
+    /// Removes a testcase by id.
     #[cfg(feature = "corpus_btreemap")]
-    pub fn remove(&self, idx: CorpusId) -> Option<&RefCell<Testcase<I>>> {
+    pub fn remove(&mut self, idx: CorpusId) -> Option<RefCell<Testcase<I>>> {
         self.map.remove(&idx)
     }
 
@@ -195,6 +199,65 @@ where
             ..Default::default()
         }
     }
+
+    /// Returns an iterator over the ids in `bounds`, in ascending `CorpusId` order.
+    ///
+    /// Useful for neighborhood/power-schedule strategies that want "the N ids around the
+    /// current one", or to shard the corpus across workers. Ids that were removed are skipped;
+    /// if the lower bound no longer exists, the iterator is empty.
+    #[cfg(feature = "corpus_btreemap")]
+    pub fn range<R>(&self, bounds: R) -> impl Iterator<Item = CorpusId> + '_
+    where
+        R: core::ops::RangeBounds<CorpusId>,
+    {
+        use core::ops::Bound;
+
+        let start_exists = match bounds.start_bound() {
+            Bound::Included(id) | Bound::Excluded(id) => self.map.contains_key(id),
+            Bound::Unbounded => true,
+        };
+        self.map
+            .range(bounds)
+            .take(if start_exists { usize::MAX } else { 0 })
+            .map(|(id, _)| *id)
+    }
+
+    /// Returns an iterator over the ids in `bounds`, in ascending `CorpusId` order.
+    ///
+    /// Useful for neighborhood/power-schedule strategies that want "the N ids around the
+    /// current one", or to shard the corpus across workers. Ids that were removed are skipped;
+    /// if the lower bound no longer exists, the iterator is empty.
+    ///
+    /// This walks the `prev`/`next` chain maintained by [`Self::insert`]/[`Self::remove`], which
+    /// is kept hole-free: every `remove` repairs its neighbors' links, so `next(id)` for a live
+    /// `id` always yields another live id or `None`. Only the starting id (which the caller may
+    /// have gotten from elsewhere, e.g. a bound computed before a removal) needs an explicit
+    /// liveness check.
+    #[cfg(not(feature = "corpus_btreemap"))]
+    pub fn range<R>(&self, bounds: R) -> impl Iterator<Item = CorpusId> + '_
+    where
+        R: core::ops::RangeBounds<CorpusId>,
+    {
+        use core::ops::Bound;
+
+        let start = match bounds.start_bound() {
+            Bound::Included(id) => Some(*id),
+            Bound::Excluded(id) => self.next(*id),
+            Bound::Unbounded => self.first_idx,
+        };
+
+        let mut cur = start.filter(|id| self.map.contains_key(id));
+
+        core::iter::from_fn(move || {
+            let id = cur?;
+            if !bounds.contains(&id) {
+                cur = None;
+                return None;
+            }
+            cur = self.next(id);
+            Some(id)
+        })
+    }
 }
 
 /// A corpus handling all in memory.
@@ -244,7 +307,7 @@ where
     /// Removes an entry from the corpus, returning it if it was present.
     #[inline]
     fn remove(&mut self, idx: CorpusId) -> Result<Option<Testcase<I>>, Error> {
-        Ok(self.entries.map.remove(&idx).map(|x| x.take()))
+        Ok(self.entries.remove(idx).map(RefCell::into_inner))
     }
 
     /// Get by id
@@ -302,6 +365,1003 @@ where
             current: None,
         }
     }
+
+    /// Surfaces [`TestcaseStorage::range`] on the corpus: a cheap iterator over a contiguous
+    /// slice of ids, e.g. "the N ids around the current one" for neighborhood/power-schedule
+    /// strategies, or to shard the corpus across workers.
+    pub fn range<R>(&self, bounds: R) -> impl Iterator<Item = CorpusId> + '_
+    where
+        R: core::ops::RangeBounds<CorpusId>,
+    {
+        self.entries.range(bounds)
+    }
+}
+
+/// A secondary index over a [`TestcaseStorage`] that keeps [`CorpusId`]s sorted by a
+/// runtime comparator instead of by `CorpusId` itself, in the style of the `copse` crate's
+/// comparator-keyed `BTreeSet`. Since a standard `BTreeSet` can only order by the element
+/// type's own `Ord`, the index instead stores plain ids and, on every comparison, looks the
+/// corresponding [`Testcase`]s up in the backing storage to run the comparator. Ties are
+/// broken by `CorpusId` so the order stays total even when the comparator considers two
+/// entries equal.
+///
+/// The comparator is expected to stay consistent for an entry's score between [`reorder`](OrderedCorpus::reorder) calls; if the
+/// underlying metadata changes, call `reorder` to re-position it.
+pub struct ComparatorIndex<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>, &Testcase<I>) -> Ordering,
+{
+    /// Ids in fitness order, best (per `cmp`) first
+    order: Vec<CorpusId>,
+    cmp: F,
+    phantom: core::marker::PhantomData<I>,
+}
+
+impl<I, F> ComparatorIndex<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>, &Testcase<I>) -> Ordering,
+{
+    /// Creates a new, empty index ordered by `cmp`.
+    pub fn new(cmp: F) -> Self {
+        Self {
+            order: Vec::new(),
+            cmp,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Compares the testcases behind two ids, breaking ties by `CorpusId`.
+    fn cmp_ids(&mut self, storage: &TestcaseStorage<I>, a: CorpusId, b: CorpusId) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
+        }
+        let ta = storage.get(a).expect("id must be present in storage").borrow();
+        let tb = storage.get(b).expect("id must be present in storage").borrow();
+        (self.cmp)(&ta, &tb).then_with(|| a.cmp(&b))
+    }
+
+    /// Binary-searches for `id`'s position, assuming the index is already sorted.
+    fn search(&mut self, storage: &TestcaseStorage<I>, id: CorpusId) -> Result<usize, usize> {
+        let mut lo = 0usize;
+        let mut hi = self.order.len();
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let candidate = self.order[mid];
+            match self.cmp_ids(storage, candidate, id) {
+                Ordering::Less => lo = mid + 1,
+                Ordering::Equal => return Ok(mid),
+                Ordering::Greater => hi = mid,
+            }
+        }
+        Err(lo)
+    }
+
+    /// Inserts `id` into the index, keeping it sorted by `cmp`.
+    pub fn insert(&mut self, storage: &TestcaseStorage<I>, id: CorpusId) {
+        let pos = self.search(storage, id).unwrap_or_else(|pos| pos);
+        self.order.insert(pos, id);
+    }
+
+    /// Removes `id` from the index, if present.
+    ///
+    /// Locates it by identity (a linear scan) rather than by binary-searching with the
+    /// comparator: by the time an entry is removed its score may already have moved on (this is
+    /// exactly what [`reorder`](Self::reorder) does), so re-deriving its position from the
+    /// *current* score would search the Vec in the wrong place and silently fail to find it.
+    pub fn remove(&mut self, id: CorpusId) {
+        if let Some(pos) = self.order.iter().position(|&x| x == id) {
+            self.order.remove(pos);
+        }
+    }
+
+    /// Re-positions `id` after its score has changed: removes it from its old (now stale)
+    /// position by identity, then binary-search-inserts it at the position its new score
+    /// implies.
+    pub fn reorder(&mut self, storage: &TestcaseStorage<I>, id: CorpusId) {
+        self.remove(id);
+        self.insert(storage, id);
+    }
+
+    /// The id ordered first (the "best" entry by `cmp`), if any.
+    pub fn first(&self) -> Option<CorpusId> {
+        self.order.first().copied()
+    }
+
+    /// The id ordered last (the "worst" entry by `cmp`), if any.
+    pub fn last(&self) -> Option<CorpusId> {
+        self.order.last().copied()
+    }
+
+    /// The id following `id` in fitness order.
+    pub fn next(&self, id: CorpusId) -> Option<CorpusId> {
+        let pos = self.order.iter().position(|&x| x == id)?;
+        self.order.get(pos + 1).copied()
+    }
+
+    /// The id preceding `id` in fitness order.
+    pub fn prev(&self, id: CorpusId) -> Option<CorpusId> {
+        let pos = self.order.iter().position(|&x| x == id)?;
+        pos.checked_sub(1).and_then(|p| self.order.get(p).copied())
+    }
+}
+
+/// A corpus whose `first`/`last`/`next`/`prev` walk in a caller-defined fitness order (e.g.
+/// ascending execution time, descending coverage novelty, favored-first) instead of insertion
+/// order, so a scheduler can iterate best-to-worst without re-sorting on every pass.
+///
+/// Random access (`get`/`remove`) still goes through the usual [`TestcaseStorage`]; a
+/// [`ComparatorIndex`] is maintained alongside it as entries are added, replaced, or removed.
+/// Since execution only updates a [`Testcase`]'s metadata (e.g. exec time) after the fact, call
+/// [`reorder`](Self::reorder) whenever an entry's score may have changed.
+pub struct OrderedCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>, &Testcase<I>) -> Ordering,
+{
+    entries: TestcaseStorage<I>,
+    index: ComparatorIndex<I, F>,
+    current: Option<CorpusId>,
+}
+
+impl<I, F> UsesInput for OrderedCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>, &Testcase<I>) -> Ordering,
+{
+    type Input = I;
+}
+
+impl<I, F> Corpus for OrderedCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>, &Testcase<I>) -> Ordering,
+{
+    /// Returns the number of elements
+    #[inline]
+    fn count(&self) -> usize {
+        self.entries.map.len()
+    }
+
+    /// Add an entry to the corpus and return its index
+    #[inline]
+    fn add(&mut self, testcase: Testcase<I>) -> Result<usize, Error> {
+        let idx = self.entries.insert(RefCell::new(testcase));
+        self.index.insert(&self.entries, idx);
+        Ok(idx)
+    }
+
+    /// Replaces the testcase at the given idx
+    #[inline]
+    fn replace(&mut self, idx: CorpusId, testcase: Testcase<I>) -> Result<Testcase<I>, Error> {
+        if let Some(entry) = self.entries.map.get_mut(&idx) {
+            let prev = entry.replace(testcase);
+            self.index.reorder(&self.entries, idx);
+            Ok(prev)
+        } else {
+            Err(Error::key_not_found(format!("Index {idx} not found")))
+        }
+    }
+
+    /// Removes an entry from the corpus, returning it if it was present.
+    #[inline]
+    fn remove(&mut self, idx: CorpusId) -> Result<Option<Testcase<I>>, Error> {
+        self.index.remove(idx);
+        Ok(self.entries.remove(idx).map(RefCell::into_inner))
+    }
+
+    /// Get by id
+    #[inline]
+    fn get(&self, idx: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        self.entries
+            .map
+            .get(&idx)
+            .ok_or_else(|| Error::key_not_found(format!("Index {idx} not found")))
+    }
+
+    /// Current testcase scheduled
+    #[inline]
+    fn current(&self) -> &Option<CorpusId> {
+        &self.current
+    }
+
+    /// Current testcase scheduled (mutable)
+    #[inline]
+    fn current_mut(&mut self) -> &mut Option<CorpusId> {
+        &mut self.current
+    }
+
+    /// Walks the fitness order rather than insertion order
+    #[inline]
+    fn next(&self, idx: CorpusId) -> Option<CorpusId> {
+        self.index.next(idx)
+    }
+
+    /// Walks the fitness order rather than insertion order
+    #[inline]
+    fn prev(&self, idx: CorpusId) -> Option<CorpusId> {
+        self.index.prev(idx)
+    }
+
+    /// The best entry by `cmp`
+    #[inline]
+    fn first(&self) -> Option<CorpusId> {
+        self.index.first()
+    }
+
+    /// The worst entry by `cmp`
+    #[inline]
+    fn last(&self) -> Option<CorpusId> {
+        self.index.last()
+    }
+}
+
+impl<I, F> OrderedCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>, &Testcase<I>) -> Ordering,
+{
+    /// Creates a new [`OrderedCorpus`], iterating `first`/`last`/`next`/`prev` in the order
+    /// imposed by `cmp` instead of insertion order.
+    #[must_use]
+    pub fn new(cmp: F) -> Self {
+        Self {
+            entries: TestcaseStorage::new(),
+            index: ComparatorIndex::new(cmp),
+            current: None,
+        }
+    }
+
+    /// Re-positions `idx` in the fitness order after its score has changed, e.g. because a
+    /// scheduler updated exec-time or novelty metadata on the underlying [`Testcase`].
+    pub fn reorder(&mut self, idx: CorpusId) {
+        self.index.reorder(&self.entries, idx);
+    }
+
+    /// Surfaces [`TestcaseStorage::range`]: a cheap iterator over a contiguous slice of ids in
+    /// insertion/`CorpusId` order, independent of the fitness order `first`/`next` walk.
+    pub fn range<R>(&self, bounds: R) -> impl Iterator<Item = CorpusId> + '_
+    where
+        R: core::ops::RangeBounds<CorpusId>,
+    {
+        self.entries.range(bounds)
+    }
+}
+
+/// What an [`InMemoryCapacityCorpus`] caps itself to.
+#[derive(Debug, Clone, Copy)]
+pub enum CorpusCapacity {
+    /// Cap the number of resident test cases
+    Count(usize),
+    /// Cap the approximate total size, in bytes, of resident inputs
+    Bytes(usize),
+}
+
+/// Per-entry storage for [`InMemoryCapacityCorpus`]: the testcase itself, its place in the
+/// insertion-ordered `prev`/`next` list (mirroring [`TestcaseStorageItem`]), and its place in a
+/// second `lru_prev`/`lru_next` list tracking recency of access.
+struct CapacityTestcaseStorageItem<I>
+where
+    I: Input,
+{
+    testcase: RefCell<Testcase<I>>,
+    size: usize,
+    prev: Option<CorpusId>,
+    next: Option<CorpusId>,
+    lru_prev: Cell<Option<CorpusId>>,
+    lru_next: Cell<Option<CorpusId>>,
+}
+
+/// An in-memory corpus that caps either the resident testcase count or an approximate byte
+/// budget, evicting the least-recently-used entry when the cap is exceeded.
+///
+/// Unlike [`InMemoryCorpus`], whose test progress is lost on OOM, this corpus bounds its memory
+/// footprint: once `add`/[`replace`](Corpus::replace) pushes the corpus over its
+/// [`CorpusCapacity`], the entry at the LRU end of a second `lru_prev`/`lru_next` list is
+/// evicted and handed to a configurable sink (e.g. to spill it into an on-disk corpus instead of
+/// dropping it). A `get`/`get_touch` of an evicted id returns `key_not_found`.
+///
+/// The LRU links live behind [`Cell`]s specifically so that [`Corpus::get`] — a `&self` trait
+/// method — can still move an entry to the MRU end; every `get`/`get_touch`/`replace` counts as
+/// an access. [`Self::get_touch`] is kept around as an explicit, non-trait spelling of the same
+/// touching `get`, for callers that want to be unambiguous about it.
+pub struct InMemoryCapacityCorpus<I>
+where
+    I: Input,
+{
+    map: hashbrown::HashMap<CorpusId, CapacityTestcaseStorageItem<I>>,
+    progressive_idx: usize,
+    first_idx: Option<CorpusId>,
+    last_idx: Option<CorpusId>,
+    lru_head: Cell<Option<CorpusId>>,
+    lru_tail: Cell<Option<CorpusId>>,
+    current: Option<CorpusId>,
+    cap: CorpusCapacity,
+    size_bytes: usize,
+    on_evict: Option<Box<dyn FnMut(CorpusId, Testcase<I>) -> Result<(), Error>>>,
+}
+
+impl<I> UsesInput for InMemoryCapacityCorpus<I>
+where
+    I: Input,
+{
+    type Input = I;
+}
+
+impl<I> InMemoryCapacityCorpus<I>
+where
+    I: Input + HasLen,
+{
+    /// Creates a new corpus capped at `max_count` resident test cases, evicting the
+    /// least-recently-used entry via `on_evict` (or dropping it, if `None`) once exceeded.
+    #[must_use]
+    pub fn with_max_count(
+        max_count: usize,
+        on_evict: Option<Box<dyn FnMut(CorpusId, Testcase<I>) -> Result<(), Error>>>,
+    ) -> Self {
+        Self::with_capacity(CorpusCapacity::Count(max_count), on_evict)
+    }
+
+    /// Creates a new corpus capped at an approximate `max_bytes` of resident input data,
+    /// evicting the least-recently-used entry via `on_evict` (or dropping it, if `None`) once
+    /// exceeded.
+    #[must_use]
+    pub fn with_max_bytes(
+        max_bytes: usize,
+        on_evict: Option<Box<dyn FnMut(CorpusId, Testcase<I>) -> Result<(), Error>>>,
+    ) -> Self {
+        Self::with_capacity(CorpusCapacity::Bytes(max_bytes), on_evict)
+    }
+
+    fn with_capacity(
+        cap: CorpusCapacity,
+        on_evict: Option<Box<dyn FnMut(CorpusId, Testcase<I>) -> Result<(), Error>>>,
+    ) -> Self {
+        Self {
+            map: hashbrown::HashMap::default(),
+            progressive_idx: 0,
+            first_idx: None,
+            last_idx: None,
+            lru_head: Cell::new(None),
+            lru_tail: Cell::new(None),
+            current: None,
+            cap,
+            size_bytes: 0,
+            on_evict,
+        }
+    }
+
+    fn entry_size(testcase: &Testcase<I>) -> usize {
+        testcase.input().as_ref().map_or(0, HasLen::len)
+    }
+
+    /// Detaches `id` from the LRU list, patching up its neighbours (or the head/tail).
+    fn lru_unlink(&self, id: CorpusId) {
+        let item = self.map.get(&id).unwrap();
+        let (prev, next) = (item.lru_prev.get(), item.lru_next.get());
+        match prev {
+            Some(prev) => self.map.get(&prev).unwrap().lru_next.set(next),
+            None => self.lru_head.set(next),
+        }
+        match next {
+            Some(next) => self.map.get(&next).unwrap().lru_prev.set(prev),
+            None => self.lru_tail.set(prev),
+        }
+    }
+
+    /// Moves `id` to the MRU end of the LRU list. Takes `&self`: the LRU links are [`Cell`]s so
+    /// this can be called from [`Corpus::get`].
+    fn lru_touch(&self, id: CorpusId) {
+        if self.lru_head.get() == Some(id) {
+            return;
+        }
+        // Only unlink if `id` is actually threaded into the list already: a freshly-inserted
+        // node starts with `lru_prev`/`lru_next` both `None` (and isn't `lru_head`/`lru_tail`
+        // yet), which `lru_unlink` would otherwise misread as "the sole node", wiping out the
+        // real head/tail and orphaning the rest of the list.
+        let linked = {
+            let item = self.map.get(&id).unwrap();
+            item.lru_prev.get().is_some()
+                || item.lru_next.get().is_some()
+                || self.lru_tail.get() == Some(id)
+        };
+        if linked {
+            self.lru_unlink(id);
+        }
+        let old_head = self.lru_head.get();
+        if let Some(old_head) = old_head {
+            self.map.get(&old_head).unwrap().lru_prev.set(Some(id));
+        }
+        {
+            let item = self.map.get(&id).unwrap();
+            item.lru_next.set(old_head);
+            item.lru_prev.set(None);
+        }
+        self.lru_head.set(Some(id));
+        if self.lru_tail.get().is_none() {
+            self.lru_tail.set(Some(id));
+        }
+    }
+
+    /// Unlinks `id` from the insertion-ordered list (mirrors [`TestcaseStorageItem`]'s removal).
+    fn unlink(&mut self, id: CorpusId) {
+        let (prev, next) = {
+            let item = self.map.get(&id).unwrap();
+            (item.prev, item.next)
+        };
+        match prev {
+            Some(prev) => self.map.get_mut(&prev).unwrap().next = next,
+            None => self.first_idx = next,
+        }
+        match next {
+            Some(next) => self.map.get_mut(&next).unwrap().prev = prev,
+            None => self.last_idx = prev,
+        }
+    }
+
+    /// Evicts the LRU entry, handing it to `on_evict` if set. No-ops on an empty corpus.
+    fn evict_one(&mut self) -> Result<(), Error> {
+        let Some(id) = self.lru_tail.get() else {
+            return Ok(());
+        };
+        self.lru_unlink(id);
+        self.unlink(id);
+        let item = self.map.remove(&id).unwrap();
+        self.size_bytes -= item.size;
+        if let Some(sink) = &mut self.on_evict {
+            sink(id, item.testcase.into_inner())?;
+        }
+        Ok(())
+    }
+
+    /// Evicts LRU entries until the corpus is back under its capacity.
+    fn evict_until_under_capacity(&mut self) -> Result<(), Error> {
+        loop {
+            let over = match self.cap {
+                CorpusCapacity::Count(max) => self.map.len() > max,
+                CorpusCapacity::Bytes(max) => self.size_bytes > max,
+            };
+            if !over || self.map.is_empty() {
+                break;
+            }
+            self.evict_one()?;
+        }
+        Ok(())
+    }
+
+    /// Looks up `idx`, moving it to the MRU end of the LRU list. A miss (the id has been
+    /// evicted) surfaces as `key_not_found`; pair this with a companion on-disk corpus lookup to
+    /// re-load an evicted case, if desired.
+    ///
+    /// Equivalent to [`Corpus::get`] on this type; kept as an explicit, non-trait spelling for
+    /// callers that want to be unambiguous about touching.
+    pub fn get_touch(&self, idx: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        if self.map.contains_key(&idx) {
+            self.lru_touch(idx);
+        }
+        self.map
+            .get(&idx)
+            .map(|item| &item.testcase)
+            .ok_or_else(|| Error::key_not_found(format!("Index {idx} not found")))
+    }
+}
+
+impl<I> Corpus for InMemoryCapacityCorpus<I>
+where
+    I: Input + HasLen,
+{
+    /// Returns the number of resident elements
+    #[inline]
+    fn count(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Adds an entry, evicting LRU entries afterwards if the corpus is now over capacity
+    fn add(&mut self, testcase: Testcase<I>) -> Result<usize, Error> {
+        let idx = CorpusId::from(self.progressive_idx);
+        self.progressive_idx += 1;
+        let size = Self::entry_size(&testcase);
+
+        let prev = self.last_idx;
+        if let Some(last_idx) = self.last_idx {
+            self.map.get_mut(&last_idx).unwrap().next = Some(idx);
+        } else {
+            self.first_idx = Some(idx);
+        }
+        self.last_idx = Some(idx);
+
+        self.map.insert(
+            idx,
+            CapacityTestcaseStorageItem {
+                testcase: RefCell::new(testcase),
+                size,
+                prev,
+                next: None,
+                lru_prev: Cell::new(None),
+                lru_next: Cell::new(None),
+            },
+        );
+        self.size_bytes += size;
+        self.lru_touch(idx);
+
+        self.evict_until_under_capacity()?;
+
+        Ok(idx)
+    }
+
+    /// Replaces the testcase at the given idx, moving it to the MRU end
+    fn replace(&mut self, idx: CorpusId, testcase: Testcase<I>) -> Result<Testcase<I>, Error> {
+        let new_size = Self::entry_size(&testcase);
+        let Some(item) = self.map.get_mut(&idx) else {
+            return Err(Error::key_not_found(format!("Index {idx} not found")));
+        };
+        let old_size = item.size;
+        let prev = item.testcase.replace(testcase);
+        item.size = new_size;
+        self.size_bytes = self.size_bytes - old_size + new_size;
+        self.lru_touch(idx);
+        self.evict_until_under_capacity()?;
+        Ok(prev)
+    }
+
+    /// Removes a resident entry, returning it. An id that was already evicted (or never
+    /// existed) yields `Ok(None)`; this corpus does not reach into the eviction sink.
+    fn remove(&mut self, idx: CorpusId) -> Result<Option<Testcase<I>>, Error> {
+        if !self.map.contains_key(&idx) {
+            return Ok(None);
+        }
+        self.lru_unlink(idx);
+        self.unlink(idx);
+        let item = self.map.remove(&idx).unwrap();
+        self.size_bytes -= item.size;
+        Ok(Some(item.testcase.into_inner()))
+    }
+
+    /// Gets by id, moving it to the MRU end of the LRU list. The LRU links live in [`Cell`]s
+    /// specifically so this `&self` trait method can still touch recency; see
+    /// [`Self::get_touch`] for an explicit, non-trait spelling of the same behavior.
+    #[inline]
+    fn get(&self, idx: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        if self.map.contains_key(&idx) {
+            self.lru_touch(idx);
+        }
+        self.map
+            .get(&idx)
+            .map(|item| &item.testcase)
+            .ok_or_else(|| Error::key_not_found(format!("Index {idx} not found")))
+    }
+
+    /// Current testcase scheduled
+    #[inline]
+    fn current(&self) -> &Option<CorpusId> {
+        &self.current
+    }
+
+    /// Current testcase scheduled (mutable)
+    #[inline]
+    fn current_mut(&mut self) -> &mut Option<CorpusId> {
+        &mut self.current
+    }
+
+    #[inline]
+    fn next(&self, idx: CorpusId) -> Option<CorpusId> {
+        self.map.get(&idx).and_then(|item| item.next)
+    }
+
+    #[inline]
+    fn prev(&self, idx: CorpusId) -> Option<CorpusId> {
+        self.map.get(&idx).and_then(|item| item.prev)
+    }
+
+    #[inline]
+    fn first(&self) -> Option<CorpusId> {
+        self.first_idx
+    }
+
+    #[inline]
+    fn last(&self) -> Option<CorpusId> {
+        self.last_idx
+    }
+}
+
+/// A total-ordered wrapper around `f64` scores, so they can key a [`alloc::collections::BinaryHeap`]
+/// (which requires `Ord`). Uses `f64::total_cmp`, so `NaN` scores sort consistently rather than
+/// breaking the heap invariant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeapScore(f64);
+
+impl Eq for HeapScore {}
+
+impl PartialOrd for HeapScore {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapScore {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/// A max-heap entry: a score, the id it was computed for, and the id's version at push time, so
+/// a popped entry whose version has since moved on (the id was re-scored or removed) can be
+/// recognized as stale and discarded instead of treated as the current best.
+struct HeapEntry {
+    score: HeapScore,
+    id: CorpusId,
+    version: u64,
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.cmp(&other.score)
+    }
+}
+
+/// Per-entry storage for [`HeapCorpus`]: the testcase plus a version counter bumped on every
+/// re-score or removal, so stale heap entries can be recognized and discarded lazily on pop.
+struct HeapTestcaseStorageItem<I>
+where
+    I: Input,
+{
+    testcase: RefCell<Testcase<I>>,
+    version: u64,
+}
+
+/// A corpus whose primary operation is "give me the single best test case right now", backed by
+/// a binary max-heap keyed by a score extracted from each [`Testcase`]'s metadata (coverage
+/// gain, rarity, handicap, ...).
+///
+/// Random access (`get`/`remove`) goes through a plain `HashMap`, same as [`InMemoryCorpus`].
+/// Since scores mutate after execution, the heap uses lazy deletion: every push carries the id's
+/// current version counter, and a popped entry whose version no longer matches the map (because
+/// the id was re-scored via [`reorder`](Self::reorder) or removed since) is discarded instead of
+/// treated as the current best. This lets callers build greedy best-first loops without scanning
+/// the whole corpus every iteration.
+///
+/// `first`/`next`/`prev`/`last` aren't meaningful for a heap order and always return `None`; use
+/// [`peek_best`](Self::peek_best)/[`pop_best`](Self::pop_best) instead.
+pub struct HeapCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>) -> f64,
+{
+    map: hashbrown::HashMap<CorpusId, HeapTestcaseStorageItem<I>>,
+    heap: alloc::collections::BinaryHeap<HeapEntry>,
+    progressive_idx: usize,
+    current: Option<CorpusId>,
+    score: F,
+}
+
+impl<I, F> UsesInput for HeapCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>) -> f64,
+{
+    type Input = I;
+}
+
+impl<I, F> HeapCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>) -> f64,
+{
+    /// Creates a new, empty [`HeapCorpus`] scored by `score`.
+    #[must_use]
+    pub fn new(score: F) -> Self {
+        Self {
+            map: hashbrown::HashMap::default(),
+            heap: alloc::collections::BinaryHeap::new(),
+            progressive_idx: 0,
+            current: None,
+            score,
+        }
+    }
+
+    /// Scores `id`'s current testcase and pushes it onto the heap tagged with its current
+    /// version.
+    fn push(&mut self, id: CorpusId) {
+        let item = self.map.get(&id).expect("id must be present in storage");
+        let score = (self.score)(&item.testcase.borrow());
+        self.heap.push(HeapEntry {
+            score: HeapScore(score),
+            id,
+            version: item.version,
+        });
+    }
+
+    /// Re-scores `id` and re-pushes it onto the heap. Any heap entry already referencing its
+    /// previous version becomes stale and is discarded lazily the next time it's popped.
+    pub fn reorder(&mut self, id: CorpusId) {
+        if let Some(item) = self.map.get_mut(&id) {
+            item.version += 1;
+            self.push(id);
+        }
+    }
+
+    /// Returns the id of the current best entry without removing it, discarding stale heap
+    /// entries encountered along the way.
+    pub fn peek_best(&mut self) -> Option<CorpusId> {
+        while let Some(top) = self.heap.peek() {
+            match self.map.get(&top.id) {
+                Some(item) if item.version == top.version => return Some(top.id),
+                _ => {
+                    self.heap.pop();
+                }
+            }
+        }
+        None
+    }
+
+    /// Removes and returns the id of the current best entry from heap contention, discarding
+    /// stale heap entries encountered along the way. The testcase itself is untouched and
+    /// remains reachable via [`Corpus::get`]; call [`reorder`](Self::reorder) to make it
+    /// eligible for `pop_best` again.
+    pub fn pop_best(&mut self) -> Option<CorpusId> {
+        loop {
+            let top = self.heap.pop()?;
+            if self
+                .map
+                .get(&top.id)
+                .is_some_and(|item| item.version == top.version)
+            {
+                return Some(top.id);
+            }
+        }
+    }
+}
+
+impl<I, F> Corpus for HeapCorpus<I, F>
+where
+    I: Input,
+    F: FnMut(&Testcase<I>) -> f64,
+{
+    /// Returns the number of elements
+    #[inline]
+    fn count(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Add an entry to the corpus and return its index
+    fn add(&mut self, testcase: Testcase<I>) -> Result<usize, Error> {
+        let idx = CorpusId::from(self.progressive_idx);
+        self.progressive_idx += 1;
+        self.map.insert(
+            idx,
+            HeapTestcaseStorageItem {
+                testcase: RefCell::new(testcase),
+                version: 0,
+            },
+        );
+        self.push(idx);
+        Ok(idx)
+    }
+
+    /// Replaces the testcase at the given idx and re-scores it
+    fn replace(&mut self, idx: CorpusId, testcase: Testcase<I>) -> Result<Testcase<I>, Error> {
+        let Some(item) = self.map.get_mut(&idx) else {
+            return Err(Error::key_not_found(format!("Index {idx} not found")));
+        };
+        let prev = item.testcase.replace(testcase);
+        item.version += 1;
+        self.push(idx);
+        Ok(prev)
+    }
+
+    /// Removes an entry from the corpus, returning it if it was present. Bumps the version so
+    /// any stale heap entry for `idx` is ignored rather than scanned for and removed.
+    fn remove(&mut self, idx: CorpusId) -> Result<Option<Testcase<I>>, Error> {
+        if let Some(item) = self.map.get_mut(&idx) {
+            item.version += 1;
+        }
+        Ok(self.map.remove(&idx).map(|item| item.testcase.into_inner()))
+    }
+
+    /// Get by id
+    #[inline]
+    fn get(&self, idx: CorpusId) -> Result<&RefCell<Testcase<I>>, Error> {
+        self.map
+            .get(&idx)
+            .map(|item| &item.testcase)
+            .ok_or_else(|| Error::key_not_found(format!("Index {idx} not found")))
+    }
+
+    /// Current testcase scheduled
+    #[inline]
+    fn current(&self) -> &Option<CorpusId> {
+        &self.current
+    }
+
+    /// Current testcase scheduled (mutable)
+    #[inline]
+    fn current_mut(&mut self) -> &mut Option<CorpusId> {
+        &mut self.current
+    }
+
+    /// Not meaningful for a heap order; always `None`. Use [`Self::peek_best`]/[`Self::pop_best`].
+    #[inline]
+    fn next(&self, _idx: CorpusId) -> Option<CorpusId> {
+        None
+    }
+
+    /// Not meaningful for a heap order; always `None`. Use [`Self::peek_best`]/[`Self::pop_best`].
+    #[inline]
+    fn prev(&self, _idx: CorpusId) -> Option<CorpusId> {
+        None
+    }
+
+    /// Not meaningful for a lazily-deleted heap: the top of the heap may be a stale entry left
+    /// behind by a `remove`/`reorder`, and discarding those requires `&mut self` (see
+    /// [`Self::peek_best`]). Always `None`; use [`Self::peek_best`] instead.
+    fn first(&self) -> Option<CorpusId> {
+        None
+    }
+
+    /// Not meaningful for a heap order; always `None`.
+    fn last(&self) -> Option<CorpusId> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::BytesInput;
+
+    fn tc(byte: u8) -> Testcase<BytesInput> {
+        Testcase::new(BytesInput::new(alloc::vec![byte]))
+    }
+
+    #[test]
+    fn capacity_corpus_evicts_lru_not_newest() {
+        let mut corpus: InMemoryCapacityCorpus<BytesInput> =
+            InMemoryCapacityCorpus::with_max_count(2, None);
+        let a = CorpusId::from(corpus.add(tc(1)).unwrap());
+        let b = CorpusId::from(corpus.add(tc(2)).unwrap());
+        // `a` is now the sole untouched (LRU) entry; pushing a third over capacity must evict
+        // it, not the freshly-added `b`. Before the `lru_touch` fix, every `add` past the first
+        // wiped the real head/tail and evicted whatever was just inserted instead.
+        let c = CorpusId::from(corpus.add(tc(3)).unwrap());
+
+        assert_eq!(corpus.count(), 2);
+        assert!(
+            corpus.get(a).is_err(),
+            "oldest untouched entry should have been evicted"
+        );
+        assert!(corpus.get(b).is_ok());
+        assert!(corpus.get(c).is_ok());
+    }
+
+    #[test]
+    fn comparator_index_reorder_keeps_order_sorted_and_total() {
+        let mut storage = TestcaseStorage::new();
+        let ids: Vec<CorpusId> = (1u8..=7)
+            .map(|byte| storage.insert(RefCell::new(tc(byte))))
+            .collect();
+
+        let mut index = ComparatorIndex::new(
+            |a: &Testcase<BytesInput>, b: &Testcase<BytesInput>| {
+                a.input().as_ref().unwrap().bytes()[0].cmp(&b.input().as_ref().unwrap().bytes()[0])
+            },
+        );
+        for &id in &ids {
+            index.insert(&storage, id);
+        }
+
+        // Bump the lowest-scored entry's score past every other entry, then reorder it. Before
+        // the identity-based `remove` fix, `search` would look for it using its *new* score
+        // while `order` was still sorted by the *old* one, fail to find it, and leave a
+        // duplicate behind.
+        let a = ids[0];
+        storage
+            .get(a)
+            .unwrap()
+            .borrow_mut()
+            .input_mut()
+            .replace(BytesInput::new(alloc::vec![100]));
+        index.reorder(&storage, a);
+
+        assert_eq!(
+            index.order.len(),
+            ids.len(),
+            "reorder must not duplicate or drop entries"
+        );
+        let scores: Vec<u8> = index
+            .order
+            .iter()
+            .map(|&id| {
+                storage
+                    .get(id)
+                    .unwrap()
+                    .borrow()
+                    .input()
+                    .as_ref()
+                    .unwrap()
+                    .bytes()[0]
+            })
+            .collect();
+        assert!(
+            scores.windows(2).all(|w| w[0] <= w[1]),
+            "order must stay sorted: {scores:?}"
+        );
+        assert_eq!(
+            index.last(),
+            Some(a),
+            "the re-scored entry should now sort last"
+        );
+    }
+
+    #[test]
+    fn range_skips_interior_removal() {
+        let mut storage = TestcaseStorage::new();
+        let ids: Vec<CorpusId> = (1u8..=5)
+            .map(|byte| storage.insert(RefCell::new(tc(byte))))
+            .collect();
+
+        // Remove a middle id. Before `remove` repaired the neighboring `prev`/`next` links,
+        // the surviving predecessor's `next` still pointed at the dead id, so `range` would
+        // yield the removed id's predecessor and then stop instead of continuing past the hole.
+        storage.remove(ids[2]);
+
+        let all: Vec<CorpusId> = storage.range(..).collect();
+        assert_eq!(
+            all,
+            alloc::vec![ids[0], ids[1], ids[3], ids[4]],
+            "range must walk past a removed interior id, not truncate there"
+        );
+
+        let inclusive: Vec<CorpusId> = storage.range(ids[1]..=ids[3]).collect();
+        assert_eq!(inclusive, alloc::vec![ids[1], ids[3]]);
+
+        let exclusive: Vec<CorpusId> = storage.range(ids[1]..ids[4]).collect();
+        assert_eq!(exclusive, alloc::vec![ids[1], ids[3]]);
+    }
+
+    #[test]
+    fn heap_corpus_pop_best_skips_stale_entries() {
+        let mut corpus: HeapCorpus<BytesInput, _> =
+            HeapCorpus::new(|t: &Testcase<BytesInput>| {
+                f64::from(t.input().as_ref().unwrap().bytes()[0])
+            });
+        let a = CorpusId::from(corpus.add(tc(1)).unwrap());
+        let b = CorpusId::from(corpus.add(tc(5)).unwrap());
+        let c = CorpusId::from(corpus.add(tc(3)).unwrap());
+
+        // `b` starts out best (score 5); bump its score further and reorder, leaving its old
+        // heap entry stale. `peek_best`/`pop_best` must discard that stale entry rather than
+        // handing back `b` a second time or getting stuck behind it.
+        corpus
+            .get(b)
+            .unwrap()
+            .borrow_mut()
+            .input_mut()
+            .replace(BytesInput::new(alloc::vec![9]));
+        corpus.reorder(b);
+        assert_eq!(corpus.peek_best(), Some(b));
+
+        // Removing the current best bumps its version, so the live heap entry for `b` is itself
+        // now stale; the next best-scored surviving id must be returned instead.
+        corpus.remove(b).unwrap();
+        assert_eq!(corpus.pop_best(), Some(c), "must skip the stale/removed id");
+        assert_eq!(corpus.pop_best(), Some(a));
+        assert_eq!(corpus.pop_best(), None);
+    }
 }
 
 /// `InMemoryCorpus` Python bindings